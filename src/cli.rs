@@ -0,0 +1,206 @@
+use std::{collections::HashMap, net::SocketAddr, path::PathBuf};
+
+use clap::Parser;
+use error_stack::{Result, ResultExt};
+use serde::Deserialize;
+use thiserror::Error;
+use tracing::Level;
+
+/// Command line arguments.
+#[derive(Debug, Parser)]
+#[command(author, version, about)]
+pub struct Args {
+    /// Path to a TOML configuration file.
+    #[arg(short, long, default_value = "config.toml")]
+    pub config: PathBuf,
+}
+
+/// Top level application configuration, loaded from the file pointed to by [`Args::config`].
+#[derive(Debug, Deserialize)]
+pub struct Config {
+    /// Address the HTTP server listens on.
+    pub address: SocketAddr,
+    /// Logging, tracing and metrics configuration.
+    pub logger: LoggerConfig,
+}
+
+impl TryFrom<Args> for Config {
+    type Error = error_stack::Report<ConfigError>;
+
+    fn try_from(args: Args) -> Result<Self, ConfigError> {
+        let content =
+            std::fs::read_to_string(&args.config).change_context(ConfigError::ReadFile)?;
+
+        toml::from_str(&content).change_context(ConfigError::Parse)
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum ConfigError {
+    #[error("failed to read configuration file")]
+    ReadFile,
+    #[error("failed to parse configuration file")]
+    Parse,
+}
+
+/// Logging, tracing and metrics configuration.
+#[derive(Debug, Deserialize)]
+pub struct LoggerConfig {
+    /// Minimum level the `fmt` layers and the OTLP layer start out at.
+    ///
+    /// Can be changed at runtime, see `LoggerExt::init_logger`.
+    #[serde(deserialize_with = "deserialize_level")]
+    pub trace_level: Level,
+    pub file: Option<FileLoggerConfig>,
+    pub stdout: Option<StdoutLoggerConfig>,
+    /// OTLP exporter configuration. Omitted entirely to skip installing OpenTelemetry export.
+    pub otlp: Option<OtlpConfig>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct FileLoggerConfig {
+    pub enabled: bool,
+    pub log_file: Option<PathBuf>,
+    pub log_amount: usize,
+    pub log_size: usize,
+    #[serde(flatten, default)]
+    pub format: FormatConfig,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct StdoutLoggerConfig {
+    pub enabled: bool,
+    #[serde(flatten, default)]
+    pub format: FormatConfig,
+}
+
+/// Output formatting shared by the file and stdout writers.
+#[derive(Debug, Default, Clone, Copy, Deserialize)]
+pub struct FormatConfig {
+    #[serde(default)]
+    pub format: LogFormat,
+    /// Include the name of the emitting thread. JSON consumers usually want this on.
+    #[serde(default)]
+    pub with_thread_names: bool,
+    /// Include the event's target (module path). JSON consumers usually want this on.
+    #[serde(default)]
+    pub with_target: bool,
+    /// Include the source line number. JSON consumers usually want this on.
+    #[serde(default)]
+    pub with_line_number: bool,
+}
+
+/// Output format for a log writer.
+#[derive(Debug, Default, Clone, Copy, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LogFormat {
+    /// Single line per event, abbreviated fields.
+    Compact,
+    /// Single line per event, full fields. The `tracing-subscriber` default.
+    #[default]
+    Full,
+    /// Multi-line, human-oriented output with each field on its own line.
+    Pretty,
+    /// Newline-delimited JSON, for ingestion by a log pipeline.
+    Json,
+}
+
+/// Configuration for the OTLP exporter used by `LoggerExt::init_metrics`.
+#[derive(Debug, Deserialize)]
+pub struct OtlpConfig {
+    /// Collector endpoint, e.g. `http://localhost:4317`.
+    pub endpoint: String,
+    /// Wire protocol used to talk to the collector.
+    #[serde(default)]
+    pub protocol: OtlpProtocol,
+    /// Extra metadata/headers sent with every export request.
+    #[serde(default)]
+    pub headers: HashMap<String, String>,
+    /// Export request timeout, in seconds.
+    pub timeout_secs: Option<u64>,
+    /// Value reported as `service.name`.
+    pub service_name: String,
+    /// Value reported as `deployment.environment`, e.g. `staging` or `production`.
+    pub environment: Option<String>,
+    /// Extra resource attributes merged in alongside the auto-detected ones.
+    #[serde(default)]
+    pub resource_attributes: HashMap<String, String>,
+    /// Trace sampling strategy. Defaults to the SDK's built-in sampler (parent-based, always on)
+    /// when omitted.
+    pub sampling: Option<SamplingConfig>,
+    /// Trace/span ID generator. Defaults to the SDK's random generator when omitted.
+    pub id_generator: Option<IdGeneratorConfig>,
+    /// Also export `tracing` events as OTLP LogRecords over this same endpoint, correlated by
+    /// trace-id with the spans exported above.
+    #[serde(default)]
+    pub export_logs: bool,
+}
+
+/// Trace sampling strategy consumed by `LoggerExt::init_metrics`.
+///
+/// `TraceIdRatio`/`ParentBasedTraceIdRatio` keep a span when the lower 8 bytes of its trace-id,
+/// interpreted as a `u64`, fall below `ratio * u64::MAX` — so `0.0` drops every root span and
+/// `1.0` keeps all of them.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum SamplingConfig {
+    /// Sample every span.
+    AlwaysOn,
+    /// Sample no spans.
+    AlwaysOff,
+    /// Sample a fraction of spans by trace-id, regardless of parent.
+    TraceIdRatio {
+        #[serde(deserialize_with = "deserialize_ratio")]
+        ratio: f64,
+    },
+    /// Honor an upstream sampling decision; only root spans consult `ratio`.
+    ParentBasedTraceIdRatio {
+        #[serde(deserialize_with = "deserialize_ratio")]
+        ratio: f64,
+    },
+}
+
+/// Trace/span ID generator selection.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum IdGeneratorConfig {
+    /// The SDK's default, non-deterministic random generator.
+    Default,
+    /// A random generator seeded with a fixed value, for reproducible ids in tests and demos.
+    Seeded { seed: u64 },
+}
+
+/// Wire protocol for the OTLP exporter.
+#[derive(Debug, Default, Clone, Copy, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OtlpProtocol {
+    /// gRPC transport via `tonic` (default, matches the collector's default receiver).
+    #[default]
+    Grpc,
+    /// OTLP/HTTP with binary protobuf bodies.
+    HttpBinary,
+}
+
+fn deserialize_level<'de, D>(deserializer: D) -> std::result::Result<Level, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let raw = String::deserialize(deserializer)?;
+    raw.parse().map_err(serde::de::Error::custom)
+}
+
+/// Rejects sampling ratios outside `0.0..=1.0` at config load time, instead of silently passing
+/// a nonsensical value (e.g. `2.0`) through to the SDK's ratio sampler.
+fn deserialize_ratio<'de, D>(deserializer: D) -> std::result::Result<f64, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let ratio = f64::deserialize(deserializer)?;
+    if (0.0..=1.0).contains(&ratio) {
+        Ok(ratio)
+    } else {
+        Err(serde::de::Error::custom(format!(
+            "sampling ratio must be between 0.0 and 1.0, got {ratio}"
+        )))
+    }
+}
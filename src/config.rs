@@ -1,18 +1,39 @@
 use std::path::PathBuf;
+use std::sync::Mutex;
 
-use crate::cli::LoggerConfig;
+use crate::cli::{
+    FormatConfig, IdGeneratorConfig, LogFormat, LoggerConfig, OtlpConfig, OtlpProtocol,
+    SamplingConfig,
+};
 
 use error_stack::{Context, Report, Result, ResultExt};
 use file_rotate::{suffix::AppendTimestamp, ContentLimit, FileRotate};
+use rand::{rngs::StdRng, Rng, SeedableRng};
 use thiserror::Error;
 use tower_http::cors::CorsLayer;
 use tracing_appender::non_blocking::{NonBlocking, WorkerGuard};
-use tracing_subscriber::{filter::LevelFilter, prelude::*, util::SubscriberInitExt};
+use tracing_subscriber::{
+    filter::{FilterExt, LevelFilter, Targets},
+    prelude::*,
+    reload,
+    util::SubscriberInitExt,
+    Layer, Registry,
+};
 
 use autometrics::objectives::{Objective, ObjectiveLatency, ObjectivePercentile};
-use opentelemetry::KeyValue;
-use opentelemetry_otlp::WithExportConfig;
-use opentelemetry_sdk::{runtime, trace as sdktrace, Resource};
+use opentelemetry::{
+    trace::{SpanId, TraceId},
+    KeyValue,
+};
+use opentelemetry_appender_tracing::layer::OpenTelemetryTracingBridge;
+use opentelemetry_otlp::{SpanExporterBuilder, WithExportConfig};
+use opentelemetry_sdk::{
+    logs::{Logger as SdkLogger, LoggerProvider},
+    runtime,
+    trace::{self as sdktrace, IdGenerator},
+    Resource,
+};
+use opentelemetry_semantic_conventions::resource as semconv;
 use tracing_opentelemetry::OpenTelemetryLayer;
 
 pub const API_SLO: Objective = Objective::new("api")
@@ -21,16 +42,75 @@ pub const API_SLO: Objective = Objective::new("api")
     // We expect 99% of all latencies to be below 250ms.
     .latency(ObjectiveLatency::Ms250, ObjectivePercentile::P99);
 
+/// Handles for adjusting log/trace verbosity after the subscriber has been installed.
+///
+/// Returned by [`LoggerExt::init_logger`] alongside the [`WorkerGuard`]s. Kept around
+/// (e.g. behind an `Arc` in application state) so an admin endpoint can call
+/// [`reload::Handle::modify`] on either handle without restarting the process.
+#[derive(Clone)]
+pub struct LoggerHandles {
+    /// Reload handle shared by every `fmt` layer (file + stdout writers).
+    pub fmt_level: reload::Handle<LevelFilter, Registry>,
+    /// Reload handle for the OpenTelemetry layer, if it was installed.
+    pub otlp_level: Option<reload::Handle<LevelFilter, Registry>>,
+}
+
+/// Flushes and shuts down the OpenTelemetry trace/log providers.
+///
+/// Returned by [`LoggerExt::init_logger`] alongside the [`WorkerGuard`]s. Call
+/// [`OtelShutdownGuard::shutdown`] once the Tokio server has stopped accepting requests, instead
+/// of just letting it drop: the SDK's shutdown calls are blocking and the OTel docs warn against
+/// invoking them from inside the async runtime that drives the batch (Tokio) processors, since
+/// they can stall waiting for a runtime worker thread that's running the very code waiting on
+/// them. `Drop` is kept only as a best-effort fallback for paths that exit without calling
+/// `shutdown` (e.g. an early `unwrap` panic).
+#[must_use]
+pub struct OtelShutdownGuard {
+    log_provider: Option<LoggerProvider>,
+}
+
+impl OtelShutdownGuard {
+    /// Flush and shut down the OpenTelemetry providers on a blocking thread, so the calling
+    /// async task isn't stalled by them.
+    pub async fn shutdown(mut self) {
+        let log_provider = self.log_provider.take();
+        let result = tokio::task::spawn_blocking(move || shutdown_otel(log_provider)).await;
+        if let Err(error) = result {
+            tracing::error!(%error, "OpenTelemetry shutdown task panicked");
+        }
+    }
+}
+
+impl Drop for OtelShutdownGuard {
+    fn drop(&mut self) {
+        shutdown_otel(self.log_provider.take());
+    }
+}
+
+/// Blocking shutdown/flush of the log provider (if any) and the global tracer provider. Must
+/// not be called from a thread that's also driving the batch processors' async tasks.
+fn shutdown_otel(log_provider: Option<LoggerProvider>) {
+    if let Some(provider) = log_provider {
+        if let Err(error) = provider.shutdown() {
+            tracing::error!(%error, "failed to shut down OTLP log provider");
+        }
+    }
+    opentelemetry::global::shutdown_tracer_provider();
+}
+
 pub trait LoggerExt {
     /// Initialize logger.
     ///
-    /// Returns [`WorkerGuard`]s for off-thread writers.
-    /// Should not be dropped.
+    /// Returns [`WorkerGuard`]s for off-thread writers, next to [`LoggerHandles`] for
+    /// runtime level changes and an [`OtelShutdownGuard`] to flush OpenTelemetry on shutdown.
+    /// None of the three should be dropped before the process is done logging.
     ///
     /// # Errors
     ///
     /// Function returns error if `init_file_rotate` fails
-    fn init_logger(&self) -> Result<Vec<WorkerGuard>, LoggerError>;
+    fn init_logger(
+        &self,
+    ) -> Result<(Vec<WorkerGuard>, LoggerHandles, OtelShutdownGuard), LoggerError>;
 
     /// Returns [`std:io::Write`] object that rotates files on write
     ///
@@ -62,43 +142,99 @@ pub trait LoggerExt {
     fn non_blocking_stdout_writer(&self) -> Result<(NonBlocking, WorkerGuard), LoggerError>;
 
     /// Init OTLP exporter
+    ///
+    /// Returns `Ok(None)` when the `otlp` section is absent from the config, so callers can
+    /// skip installing the OpenTelemetry layer entirely instead of exporting to a hardcoded
+    /// default endpoint.
     fn init_metrics<
         S: tracing::Subscriber + for<'span> tracing_subscriber::registry::LookupSpan<'span>,
     >(
         &self,
-    ) -> Result<OpenTelemetryLayer<S, opentelemetry_sdk::trace::Tracer>, LoggerError>;
+    ) -> Result<Option<OpenTelemetryLayer<S, opentelemetry_sdk::trace::Tracer>>, LoggerError>;
+
+    /// Init an OTLP logs pipeline, forwarding `tracing` events as OTLP LogRecords to the same
+    /// collector `init_metrics` exports spans to, correlated by trace-id.
+    ///
+    /// Returns `Ok(None)` when the `otlp` section is absent or `otlp.export_logs` is `false`.
+    /// The [`LoggerProvider`] is returned alongside the layer so it can be kept alive and
+    /// flushed by [`OtelShutdownGuard`] on shutdown.
+    #[allow(clippy::type_complexity)]
+    fn init_log_export(
+        &self,
+    ) -> Result<Option<(OpenTelemetryTracingBridge<LoggerProvider, SdkLogger>, LoggerProvider)>, LoggerError>;
 }
 
 impl LoggerExt for LoggerConfig {
-    fn init_logger(&self) -> Result<Vec<WorkerGuard>, LoggerError> {
+    fn init_logger(
+        &self,
+    ) -> Result<(Vec<WorkerGuard>, LoggerHandles, OtelShutdownGuard), LoggerError> {
         let mut guards = Vec::with_capacity(2);
+        let mut log_provider = None;
+
+        let file_format = self.file.as_ref().map(|config| config.format).unwrap_or_default();
+        let stdout_format = self.stdout.as_ref().map(|config| config.format).unwrap_or_default();
 
         let file_writer = disable_on_error(self.non_blocking_file_writer())?;
         let stdout_writer = disable_on_error(self.non_blocking_stdout_writer())?;
 
-        let mut layers_iter =
-            [file_writer, stdout_writer]
-                .into_iter()
-                .flatten()
-                .map(|(writer, guard)| {
-                    guards.push(guard);
-                    tracing_subscriber::fmt::layer()
-                        .with_writer(writer)
-                        .with_filter(LevelFilter::from_level(self.trace_level))
-                });
+        let (fmt_filter, fmt_level) =
+            reload::Layer::new(LevelFilter::from_level(self.trace_level));
+
+        let mut layers_iter = [
+            file_writer.map(|writer| (writer, file_format)),
+            stdout_writer.map(|writer| (writer, stdout_format)),
+        ]
+        .into_iter()
+        .flatten()
+        .map(|((writer, guard), format)| {
+            guards.push(guard);
+            build_fmt_layer(format, writer).with_filter(fmt_filter.clone())
+        });
+
+        let mut otlp_level = None;
 
         if let Some(first_layer) = layers_iter.next() {
             let layers = layers_iter.fold(first_layer.boxed(), |layer, next_layer| {
                 layer.and_then(next_layer).boxed()
             });
-            let layers = layers.and_then(
-                self.init_metrics()
-                    .change_context(LoggerError::OLTPInitFailed)?,
-            );
+
+            let layers = if let Some(otlp_layer) =
+                self.init_metrics().change_context(LoggerError::OLTPInitFailed)?
+            {
+                let (otlp_filter, handle) =
+                    reload::Layer::new(LevelFilter::from_level(self.trace_level));
+                otlp_level = Some(handle);
+                let layers = layers
+                    .and_then(otlp_layer.with_filter(otlp_filter.clone()))
+                    .boxed();
+
+                if let Some((log_layer, provider)) =
+                    self.init_log_export().change_context(LoggerError::OLTPInitFailed)?
+                {
+                    log_provider = Some(provider);
+                    // Respect the same reloadable level as the trace layer, and drop events
+                    // emitted by the exporter/transport themselves so a collector-down error
+                    // doesn't get re-exported as a log, forever re-triggering itself.
+                    let log_filter = otlp_filter.and(otlp_internal_targets());
+                    layers.and_then(log_layer.with_filter(log_filter)).boxed()
+                } else {
+                    layers
+                }
+            } else {
+                layers
+            };
+
             tracing_subscriber::registry().with(layers).init();
         };
 
-        Ok(guards)
+        Ok((
+            guards,
+            LoggerHandles {
+                fmt_level,
+                otlp_level,
+            },
+            OtelShutdownGuard { log_provider },
+        ))
     }
 
     fn init_file_rotate(&self) -> Result<FileRotate<AppendTimestamp>, LoggerError> {
@@ -147,26 +283,241 @@ impl LoggerExt for LoggerConfig {
         S: tracing::Subscriber + for<'span> tracing_subscriber::registry::LookupSpan<'span>,
     >(
         &self,
-    ) -> Result<OpenTelemetryLayer<S, opentelemetry_sdk::trace::Tracer>, LoggerError> {
+    ) -> Result<Option<OpenTelemetryLayer<S, opentelemetry_sdk::trace::Tracer>>, LoggerError> {
         autometrics::prometheus_exporter::init();
+
+        let Some(otlp) = self.otlp.as_ref() else {
+            return Ok(None);
+        };
+
+        let mut trace_config = sdktrace::config().with_resource(build_resource(otlp));
+
+        if let Some(sampling) = otlp.sampling.as_ref() {
+            trace_config = trace_config.with_sampler(build_sampler(sampling));
+        }
+
+        if let Some(IdGeneratorConfig::Seeded { seed }) = otlp.id_generator.as_ref() {
+            trace_config = trace_config.with_id_generator(SeededIdGenerator::new(*seed));
+        }
+
         let tracer = opentelemetry_otlp::new_pipeline()
             .tracing()
-            .with_exporter(
-                opentelemetry_otlp::new_exporter()
-                    .tonic()
-                    .with_endpoint("http://localhost:4317"),
-            )
-            .with_trace_config(
-                sdktrace::config()
-                    .with_resource(Resource::new(vec![KeyValue::new("service.name", "bob")])),
-            )
+            .with_exporter(build_exporter(otlp))
+            .with_trace_config(trace_config)
             .install_batch(runtime::Tokio)
             .change_context(LoggerError::OLTPInitFailed)?;
 
         let opentelemetry = tracing_opentelemetry::layer().with_tracer(tracer);
 
-        Ok(opentelemetry)
+        Ok(Some(opentelemetry))
+    }
+
+    fn init_log_export(
+        &self,
+    ) -> Result<Option<(OpenTelemetryTracingBridge<LoggerProvider, SdkLogger>, LoggerProvider)>, LoggerError>
+    {
+        let Some(otlp) = self.otlp.as_ref() else {
+            return Ok(None);
+        };
+
+        if !otlp.export_logs {
+            return Ok(None);
+        }
+
+        let exporter = build_log_exporter(otlp).change_context(LoggerError::OLTPInitFailed)?;
+
+        let provider = LoggerProvider::builder()
+            .with_batch_exporter(exporter, runtime::Tokio)
+            .with_resource(build_resource(otlp))
+            .build();
+
+        let bridge = OpenTelemetryTracingBridge::new(&provider);
+
+        Ok(Some((bridge, provider)))
+    }
+}
+
+/// Build the exporter builder for the configured protocol, applying the shared
+/// endpoint/timeout/headers options before handing it to the pipeline builder.
+fn build_exporter(otlp: &OtlpConfig) -> SpanExporterBuilder {
+    match otlp.protocol {
+        OtlpProtocol::Grpc => {
+            let mut exporter = opentelemetry_otlp::new_exporter()
+                .tonic()
+                .with_endpoint(&otlp.endpoint);
+
+            if let Some(timeout) = otlp.timeout_secs {
+                exporter = exporter.with_timeout(std::time::Duration::from_secs(timeout));
+            }
+
+            if !otlp.headers.is_empty() {
+                exporter = exporter.with_metadata(build_metadata(&otlp.headers));
+            }
+
+            exporter.into()
+        }
+        OtlpProtocol::HttpBinary => {
+            let mut exporter = opentelemetry_otlp::new_exporter()
+                .http()
+                .with_endpoint(&otlp.endpoint);
+
+            if let Some(timeout) = otlp.timeout_secs {
+                exporter = exporter.with_timeout(std::time::Duration::from_secs(timeout));
+            }
+
+            if !otlp.headers.is_empty() {
+                exporter = exporter.with_headers(otlp.headers.clone());
+            }
+
+            exporter.into()
+        }
+    }
+}
+
+/// Build the OpenTelemetry `Resource` reported with every span, merging auto-detected
+/// semantic-convention attributes with whatever the operator configured explicitly.
+fn build_resource(otlp: &OtlpConfig) -> Resource {
+    let mut attributes = vec![
+        KeyValue::new(semconv::SERVICE_NAME, otlp.service_name.clone()),
+        KeyValue::new(semconv::SERVICE_VERSION, env!("CARGO_PKG_VERSION")),
+        KeyValue::new(semconv::PROCESS_PID, i64::from(std::process::id())),
+    ];
+
+    if let Ok(host_name) = gethostname::gethostname().into_string() {
+        attributes.push(KeyValue::new(semconv::HOST_NAME, host_name));
+    }
+
+    if let Some(environment) = otlp.environment.clone() {
+        attributes.push(KeyValue::new(semconv::DEPLOYMENT_ENVIRONMENT, environment));
+    }
+
+    attributes.extend(
+        otlp.resource_attributes
+            .iter()
+            .map(|(key, value)| KeyValue::new(key.clone(), value.clone())),
+    );
+
+    Resource::new(attributes)
+}
+
+/// Build a boxed `fmt` layer for one writer, branching on its configured [`LogFormat`].
+///
+/// Each `.json()`/`.pretty()`/`.compact()` call changes the layer's static type, so it's boxed
+/// here to let differently-formatted writers still be folded into one subscriber.
+fn build_fmt_layer(
+    format: FormatConfig,
+    writer: NonBlocking,
+) -> Box<dyn Layer<Registry> + Send + Sync> {
+    let layer = tracing_subscriber::fmt::layer()
+        .with_writer(writer)
+        .with_thread_names(format.with_thread_names)
+        .with_target(format.with_target)
+        .with_line_number(format.with_line_number);
+
+    match format.format {
+        LogFormat::Compact => layer.compact().boxed(),
+        LogFormat::Full => layer.boxed(),
+        LogFormat::Pretty => layer.pretty().boxed(),
+        LogFormat::Json => layer.json().boxed(),
+    }
+}
+
+/// Build the `Sampler` for the configured strategy.
+fn build_sampler(sampling: &SamplingConfig) -> sdktrace::Sampler {
+    match sampling {
+        SamplingConfig::AlwaysOn => sdktrace::Sampler::AlwaysOn,
+        SamplingConfig::AlwaysOff => sdktrace::Sampler::AlwaysOff,
+        SamplingConfig::TraceIdRatio { ratio } => sdktrace::Sampler::TraceIdRatioBased(*ratio),
+        SamplingConfig::ParentBasedTraceIdRatio { ratio } => sdktrace::Sampler::ParentBased(
+            Box::new(sdktrace::Sampler::TraceIdRatioBased(*ratio)),
+        ),
+    }
+}
+
+/// [`IdGenerator`] seeded with a fixed value, for reproducible trace/span ids in tests and demos.
+#[derive(Debug)]
+struct SeededIdGenerator(Mutex<StdRng>);
+
+impl SeededIdGenerator {
+    fn new(seed: u64) -> Self {
+        Self(Mutex::new(StdRng::seed_from_u64(seed)))
+    }
+}
+
+impl IdGenerator for SeededIdGenerator {
+    fn new_trace_id(&self) -> TraceId {
+        let mut rng = self.0.lock().unwrap_or_else(std::sync::PoisonError::into_inner);
+        TraceId::from_bytes(rng.gen())
+    }
+
+    fn new_span_id(&self) -> SpanId {
+        let mut rng = self.0.lock().unwrap_or_else(std::sync::PoisonError::into_inner);
+        SpanId::from_bytes(rng.gen())
+    }
+}
+
+/// Build the log exporter for the configured protocol, mirroring `build_exporter`.
+fn build_log_exporter(
+    otlp: &OtlpConfig,
+) -> std::result::Result<opentelemetry_otlp::LogExporter, opentelemetry::logs::LogError> {
+    match otlp.protocol {
+        OtlpProtocol::Grpc => {
+            let mut exporter = opentelemetry_otlp::new_exporter()
+                .tonic()
+                .with_endpoint(&otlp.endpoint);
+
+            if let Some(timeout) = otlp.timeout_secs {
+                exporter = exporter.with_timeout(std::time::Duration::from_secs(timeout));
+            }
+
+            if !otlp.headers.is_empty() {
+                exporter = exporter.with_metadata(build_metadata(&otlp.headers));
+            }
+
+            exporter.build_log_exporter()
+        }
+        OtlpProtocol::HttpBinary => {
+            let mut exporter = opentelemetry_otlp::new_exporter()
+                .http()
+                .with_endpoint(&otlp.endpoint);
+
+            if let Some(timeout) = otlp.timeout_secs {
+                exporter = exporter.with_timeout(std::time::Duration::from_secs(timeout));
+            }
+
+            if !otlp.headers.is_empty() {
+                exporter = exporter.with_headers(otlp.headers.clone());
+            }
+
+            exporter.build_log_exporter()
+        }
+    }
+}
+
+/// Targets filter that silences the exporter/transport crates' own `tracing` events, so the
+/// OTLP log layer doesn't re-ingest e.g. "OpenTelemetry trace error occurred" and export it
+/// right back out, forever re-triggering itself when the collector is unreachable.
+fn otlp_internal_targets() -> Targets {
+    Targets::new()
+        .with_target("opentelemetry", LevelFilter::OFF)
+        .with_target("opentelemetry_otlp", LevelFilter::OFF)
+        .with_target("opentelemetry_sdk", LevelFilter::OFF)
+        .with_target("tonic", LevelFilter::OFF)
+        .with_default(LevelFilter::TRACE)
+}
+
+/// Turn a plain header map into gRPC metadata for the `tonic` exporter.
+fn build_metadata(headers: &std::collections::HashMap<String, String>) -> tonic::metadata::MetadataMap {
+    let mut metadata = tonic::metadata::MetadataMap::new();
+    for (key, value) in headers {
+        if let (Ok(key), Ok(value)) = (
+            tonic::metadata::MetadataKey::from_bytes(key.as_bytes()),
+            value.parse(),
+        ) {
+            metadata.insert(key, value);
+        }
     }
+    metadata
 }
 
 #[derive(Debug, Error)]
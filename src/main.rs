@@ -1,7 +1,10 @@
+use std::sync::Arc;
+
 use autometrics::autometrics;
-use axum::{http::StatusCode, routing::get, Router};
+use axum::{extract::State, http::StatusCode, routing::get, Router};
 use clap::Parser;
-use config::LoggerExt;
+use config::{LoggerExt, LoggerHandles};
+use tracing_subscriber::filter::LevelFilter;
 use uuid::Uuid;
 
 mod cli;
@@ -13,7 +16,8 @@ async fn main() {
 
     let logger = &config.logger;
 
-    let _logger_guards = logger.init_logger().unwrap();
+    let (_logger_guards, log_handles, otel_guard) = logger.init_logger().unwrap();
+    let log_handles = Arc::new(log_handles);
     tracing::info!("Logger: {logger:?}");
 
     let addr = config.address;
@@ -26,11 +30,90 @@ async fn main() {
                 .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)
         }),
     );
+    let app = app
+        .route("/log-level", axum::routing::put(set_fmt_log_level))
+        .route("/log-level/otlp", axum::routing::put(set_otlp_log_level))
+        .with_state(log_handles);
 
     axum::Server::bind(&config.address)
         .serve(app.into_make_service())
+        .with_graceful_shutdown(shutdown_signal())
         .await
-        .unwrap()
+        .unwrap();
+
+    // Flush and shut down the OpenTelemetry providers now that in-flight requests have
+    // finished draining. Done via `shutdown` (not just dropping the guard) since the SDK's
+    // shutdown calls are blocking and must not run directly on a Tokio runtime thread.
+    otel_guard.shutdown().await;
+    tracing::info!("Shutdown complete");
+}
+
+/// Resolves on Ctrl-C or SIGTERM, so `main` can let in-flight requests drain before calling
+/// [`config::OtelShutdownGuard::shutdown`] to flush buffered spans/logs.
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        tokio::signal::ctrl_c()
+            .await
+            .expect("failed to install Ctrl+C handler");
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
+            .expect("failed to install SIGTERM handler")
+            .recv()
+            .await;
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        () = ctrl_c => {},
+        () = terminate => {},
+    }
+
+    tracing::info!("Shutdown signal received, draining in-flight requests");
+}
+
+/// `PUT /log-level` - change the verbosity of the file/stdout `fmt` layers at runtime.
+///
+/// Body is a bare level name (`trace`, `debug`, `info`, `warn`, `error`), case-insensitive.
+async fn set_fmt_log_level(
+    State(handles): State<Arc<LoggerHandles>>,
+    level: String,
+) -> Result<StatusCode, StatusCode> {
+    let level = level
+        .trim()
+        .parse::<tracing::Level>()
+        .map_err(|_| StatusCode::BAD_REQUEST)?;
+
+    handles
+        .fmt_level
+        .modify(|filter| *filter = LevelFilter::from_level(level))
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(StatusCode::OK)
+}
+
+/// `PUT /log-level/otlp` - change the verbosity of the OpenTelemetry export layer at runtime,
+/// independently of the file/stdout `fmt` layers.
+async fn set_otlp_log_level(
+    State(handles): State<Arc<LoggerHandles>>,
+    level: String,
+) -> Result<StatusCode, StatusCode> {
+    let otlp_level = handles.otlp_level.as_ref().ok_or(StatusCode::NOT_FOUND)?;
+
+    let level = level
+        .trim()
+        .parse::<tracing::Level>()
+        .map_err(|_| StatusCode::BAD_REQUEST)?;
+
+    otlp_level
+        .modify(|filter| *filter = LevelFilter::from_level(level))
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(StatusCode::OK)
 }
 
 #[autometrics(objective = config::API_SLO)]